@@ -4,9 +4,13 @@
 //! and general things in the operation of the Linux kernel.
 
 use std::cmp;
+use std::ffi::CStr;
+use std::fmt;
 use std::str::FromStr;
 
-use crate::{read_value, ProcResult};
+use bitflags::bitflags;
+
+use crate::{read_value, write_value, ProcResult};
 
 pub mod keys;
 pub mod random;
@@ -17,23 +21,69 @@ pub struct Version {
     pub major: u8,
     pub minor: u8,
     pub patch: u8,
+    /// The release candidate number, if this is a `-rcN` pre-release.
+    ///
+    /// A pre-release sorts before the final release it leads up to, e.g.
+    /// `6.1.0-rc3` is less than `6.1.0`.
+    pub pre: Option<u32>,
 }
 
 impl Version {
     pub fn new(major: u8, minor: u8, patch: u8) -> Version {
-        Version { major, minor, patch }
+        Version {
+            major,
+            minor,
+            patch,
+            pre: None,
+        }
+    }
+
+    /// Constructs a release-candidate version, e.g. `Version::new_rc(6, 1, 0, 3)`
+    /// for `6.1.0-rc3`.
+    pub fn new_rc(major: u8, minor: u8, patch: u8, rc: u32) -> Version {
+        Version {
+            major,
+            minor,
+            patch,
+            pre: Some(rc),
+        }
     }
 
     /// Returns the kernel version of the currently running kernel.
     ///
-    /// This is taken from `/proc/sys/kernel/osrelease`;
+    /// This first tries the `uname(2)` syscall, and falls back to reading
+    /// `/proc/sys/kernel/osrelease` if that's unavailable. This means the
+    /// function works even in containers or sandboxes where `/proc` isn't
+    /// mounted or is masked.
     pub fn current() -> ProcResult<Self> {
+        if let Some(version) = Self::current_from_uname() {
+            return Ok(version);
+        }
+
         read_value("/proc/sys/kernel/osrelease")
     }
 
-    /// Parses a kernel version string, in major.minor.release syntax.
+    fn current_from_uname() -> Option<Self> {
+        unsafe {
+            let mut uts: libc::utsname = std::mem::zeroed();
+            if libc::uname(&mut uts) != 0 {
+                return None;
+            }
+
+            let release = CStr::from_ptr(uts.release.as_ptr()).to_str().ok()?;
+            Self::from_str(release).ok()
+        }
+    }
+
+    /// Parses a kernel version string, in major.minor.patch syntax.
     ///
-    /// Note that any extra information (stuff after a dash) is ignored.
+    /// Missing minor or patch components are treated as `0`, and any extra
+    /// information beyond the first three dotted components is ignored. This
+    /// is lenient enough to handle real-world release strings like `6.1`,
+    /// `5.15-generic`, or the WSL-style `5.10.102.1-microsoft-standard-WSL2`.
+    ///
+    /// A `-rcN` suffix right after the dotted version, e.g. `6.1.0-rc3`, is
+    /// parsed into [`Version::pre`]; any other stuff after a dash is ignored.
     ///
     /// # Example
     ///
@@ -56,14 +106,27 @@ impl Version {
         let mut kernel_split = kernel.split('.');
 
         let major = kernel_split.next().ok_or("Missing major version component")?;
-        let minor = kernel_split.next().ok_or("Missing minor version component")?;
-        let patch = kernel_split.next().ok_or("Missing patch version component")?;
+        let minor = kernel_split.next().unwrap_or("0");
+        let patch = kernel_split.next().unwrap_or("0");
 
         let major = major.parse().map_err(|_| "Failed to parse major version")?;
         let minor = minor.parse().map_err(|_| "Failed to parse minor version")?;
         let patch = patch.parse().map_err(|_| "Failed to parse patch version")?;
 
-        Ok(Version { major, minor, patch })
+        let pre = pos.and_then(|pos| {
+            s[pos..]
+                .strip_prefix("-rc")
+                .map(|digits| digits.find(|c: char| !c.is_ascii_digit()).map_or(digits, |end| &digits[..end]))
+                .filter(|digits| !digits.is_empty())
+                .and_then(|digits| digits.parse().ok())
+        });
+
+        Ok(Version {
+            major,
+            minor,
+            patch,
+            pre,
+        })
     }
 }
 
@@ -72,7 +135,8 @@ impl FromStr for Version {
 
     /// Parses a kernel version string, in major.minor.release syntax.
     ///
-    /// Note that any extra information (stuff after a dash) is ignored.
+    /// A `-rcN` suffix is parsed into [`Version::pre`]; any other stuff
+    /// after a dash is ignored.
     ///
     /// # Example
     ///
@@ -92,7 +156,18 @@ impl cmp::Ord for Version {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
         match self.major.cmp(&other.major) {
             cmp::Ordering::Equal => match self.minor.cmp(&other.minor) {
-                cmp::Ordering::Equal => self.patch.cmp(&other.patch),
+                cmp::Ordering::Equal => match self.patch.cmp(&other.patch) {
+                    // A pre-release (`Some`) sorts before the final release
+                    // (`None`) it leads up to, and two pre-releases compare
+                    // by their `rcN` number.
+                    cmp::Ordering::Equal => match (self.pre, other.pre) {
+                        (None, None) => cmp::Ordering::Equal,
+                        (None, Some(_)) => cmp::Ordering::Greater,
+                        (Some(_), None) => cmp::Ordering::Less,
+                        (Some(a), Some(b)) => a.cmp(&b),
+                    },
+                    x => x,
+                },
                 x => x,
             },
             x => x,
@@ -106,6 +181,225 @@ impl cmp::PartialOrd for Version {
     }
 }
 
+/// A single operator + partial version, e.g. the `>=4.9` in `>=4.9, <6.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Comparator {
+    op: ReqOp,
+    version: PartialVersion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReqOp {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Tilde,
+    Caret,
+}
+
+/// A version with optional minor/patch components, used to express
+/// constraints like `>=1.2` where the missing component is a wildcard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PartialVersion {
+    major: u8,
+    minor: Option<u8>,
+    patch: Option<u8>,
+}
+
+impl PartialVersion {
+    /// The lowest `(major, minor, patch)` this partial version can refer to,
+    /// treating missing components as zero.
+    ///
+    /// Widened to `u16` so that callers can compute an exclusive upper bound
+    /// (e.g. `minor + 1`) without overflowing, since `major`/`minor`/`patch`
+    /// are `u8` and can already be at their maximum value.
+    fn lower_bound(&self) -> (u16, u16, u16) {
+        (
+            self.major.into(),
+            self.minor.unwrap_or(0).into(),
+            self.patch.unwrap_or(0).into(),
+        )
+    }
+
+    fn parse(s: &str) -> Result<Self, VersionReqError> {
+        let mut parts = s.split('.');
+
+        let major = parts
+            .next()
+            .filter(|p| !p.is_empty())
+            .ok_or(VersionReqError::MissingVersion)?
+            .parse()
+            .map_err(|_| VersionReqError::InvalidVersion(s.to_string()))?;
+
+        let minor = match parts.next() {
+            Some(p) => Some(p.parse().map_err(|_| VersionReqError::InvalidVersion(s.to_string()))?),
+            None => None,
+        };
+
+        let patch = match parts.next() {
+            Some(p) => Some(p.parse().map_err(|_| VersionReqError::InvalidVersion(s.to_string()))?),
+            None => None,
+        };
+
+        Ok(PartialVersion { major, minor, patch })
+    }
+}
+
+impl Comparator {
+    fn parse(s: &str) -> Result<Self, VersionReqError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(VersionReqError::MissingOperator);
+        }
+
+        let (op, rest) = if let Some(rest) = s.strip_prefix(">=") {
+            (ReqOp::Gte, rest)
+        } else if let Some(rest) = s.strip_prefix("<=") {
+            (ReqOp::Lte, rest)
+        } else if let Some(rest) = s.strip_prefix('>') {
+            (ReqOp::Gt, rest)
+        } else if let Some(rest) = s.strip_prefix('<') {
+            (ReqOp::Lt, rest)
+        } else if let Some(rest) = s.strip_prefix('=') {
+            (ReqOp::Eq, rest)
+        } else if let Some(rest) = s.strip_prefix('~') {
+            (ReqOp::Tilde, rest)
+        } else if let Some(rest) = s.strip_prefix('^') {
+            (ReqOp::Caret, rest)
+        } else {
+            return Err(VersionReqError::InvalidOperator(s.to_string()));
+        };
+
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return Err(VersionReqError::MissingVersion);
+        }
+
+        Ok(Comparator {
+            op,
+            version: PartialVersion::parse(rest)?,
+        })
+    }
+
+    // `version.pre` is intentionally ignored here: comparators only
+    // constrain `major.minor.patch`, so e.g. `<6.0` matches `6.0.0-rc1` the
+    // same as it matches `6.0.0`. Pre-release ordering via `Version`'s `Ord`
+    // impl only kicks in when comparing two `Version`s directly.
+    fn matches(&self, version: &Version) -> bool {
+        let v: (u16, u16, u16) = (version.major.into(), version.minor.into(), version.patch.into());
+
+        // The exclusive upper end of the versions the partial version covers,
+        // e.g. `1.2` covers `>=1.2.0, <1.3.0` and `1.2.3` covers only `1.2.3`.
+        let lower = self.version.lower_bound();
+        let covered_upper = match (self.version.minor, self.version.patch) {
+            (None, _) => (lower.0 + 1, 0, 0),
+            (Some(_), None) => (lower.0, lower.1 + 1, 0),
+            (Some(_), Some(_)) => (lower.0, lower.1, lower.2 + 1),
+        };
+
+        match self.op {
+            // A bare `=1.2` with no patch matches any patch within `1.2`, and
+            // `=1` matches any version within major version `1`.
+            ReqOp::Eq => match (self.version.minor, self.version.patch) {
+                (None, _) => v.0 == u16::from(self.version.major),
+                (Some(minor), None) => v.0 == u16::from(self.version.major) && v.1 == u16::from(minor),
+                (Some(_), Some(_)) => v == self.version.lower_bound(),
+            },
+            // `>1.2` matches `>=1.3.0`, and `<=1.2` matches `<1.3.0`.
+            ReqOp::Gt => v >= covered_upper,
+            ReqOp::Gte => v >= self.version.lower_bound(),
+            ReqOp::Lt => v < self.version.lower_bound(),
+            ReqOp::Lte => v < covered_upper,
+            // `~1.2.3` matches `>=1.2.3, <1.3.0`; `~1.2` matches `>=1.2.0, <1.3.0`;
+            // `~1` matches `>=1.0.0, <2.0.0`.
+            ReqOp::Tilde => {
+                let lower = self.version.lower_bound();
+                let upper = if self.version.minor.is_some() {
+                    (lower.0, lower.1 + 1, 0)
+                } else {
+                    covered_upper
+                };
+                v >= lower && v < upper
+            }
+            // `^1.2.3` matches `>=1.2.3, <2.0.0`, except when major is `0`, in
+            // which case it matches `>=0.2.3, <0.3.0`.
+            ReqOp::Caret => {
+                let lower = self.version.lower_bound();
+                let upper = if lower.0 > 0 {
+                    (lower.0 + 1, 0, 0)
+                } else {
+                    (0, lower.1 + 1, 0)
+                };
+                v >= lower && v < upper
+            }
+        }
+    }
+}
+
+/// An error encountered while parsing a [`VersionReq`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionReqError {
+    /// A comparator was empty, e.g. an empty segment between commas.
+    MissingOperator,
+    /// The comparator didn't start with one of `=`, `>`, `>=`, `<`, `<=`, `~`, or `^`.
+    InvalidOperator(String),
+    /// The version part of a comparator, or its major component, was empty.
+    MissingVersion,
+    /// The version part of a comparator couldn't be parsed as `major[.minor[.patch]]`.
+    InvalidVersion(String),
+}
+
+impl fmt::Display for VersionReqError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionReqError::MissingOperator => write!(f, "missing comparator operator"),
+            VersionReqError::InvalidOperator(s) => write!(f, "invalid comparator operator: {:?}", s),
+            VersionReqError::MissingVersion => write!(f, "missing version after comparator operator"),
+            VersionReqError::InvalidVersion(s) => write!(f, "invalid version: {:?}", s),
+        }
+    }
+}
+
+impl std::error::Error for VersionReqError {}
+
+/// Represents a constraint on a [`Version`], e.g. `>=4.9, <6.0`.
+///
+/// This is far more ergonomic than hand-rolling comparisons against a
+/// [`Version`] when gating code paths on kernel features.
+///
+/// # Example
+///
+/// ```
+/// # use procfs::sys::kernel::{Version, VersionReq};
+/// let req: VersionReq = ">=4.9, <6.0".parse().unwrap();
+/// assert!(req.matches(&Version::new(5, 10, 0)));
+/// assert!(!req.matches(&Version::new(6, 1, 0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// Returns true if `version` satisfies every comparator in this requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = VersionReqError;
+
+    /// Parses a comma-separated list of comparators, e.g. `">=4.9, <6.0"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let comparators = s.split(',').map(Comparator::parse).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(VersionReq { comparators })
+    }
+}
+
 /// Returns the maximum process ID number.
 ///
 /// This is taken from `/proc/sys/kernel/pid_max`.
@@ -127,6 +421,192 @@ pub fn pid_max() -> ProcResult<i32> {
     read_value("/proc/sys/kernel/pid_max")
 }
 
+/// Returns the system-wide limit on the number of threads.
+///
+/// This is taken from `/proc/sys/kernel/threads-max`.
+pub fn threads_max() -> ProcResult<i32> {
+    read_value("/proc/sys/kernel/threads-max")
+}
+
+/// Sets the system-wide limit on the number of threads.
+///
+/// This writes to `/proc/sys/kernel/threads-max`, and typically requires root.
+pub fn set_threads_max(max: i32) -> ProcResult<()> {
+    write_value("/proc/sys/kernel/threads-max", max)
+}
+
+/// The address-space layout randomization policy, from `/proc/sys/kernel/randomize_va_space`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RandomizeVaSpace {
+    /// ASLR is disabled.
+    Disabled = 0,
+    /// The heap, mmap base, stack, and VDSO page are randomized, but a static
+    /// binary's data segment is placed right after its uninitialized data.
+    Conservative = 1,
+    /// Like [`Conservative`](Self::Conservative), but the data segment is
+    /// also placed at a random offset.
+    Full = 2,
+}
+
+impl FromStr for RandomizeVaSpace {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "0" => Ok(RandomizeVaSpace::Disabled),
+            "1" => Ok(RandomizeVaSpace::Conservative),
+            "2" => Ok(RandomizeVaSpace::Full),
+            _ => Err("Unrecognized randomize_va_space value"),
+        }
+    }
+}
+
+/// Returns the current address-space layout randomization policy.
+///
+/// This is taken from `/proc/sys/kernel/randomize_va_space`.
+pub fn randomize_va_space() -> ProcResult<RandomizeVaSpace> {
+    read_value("/proc/sys/kernel/randomize_va_space")
+}
+
+/// Sets the address-space layout randomization policy.
+///
+/// This writes to `/proc/sys/kernel/randomize_va_space`, and typically requires root.
+pub fn set_randomize_va_space(value: RandomizeVaSpace) -> ProcResult<()> {
+    write_value("/proc/sys/kernel/randomize_va_space", value as i32)
+}
+
+/// Returns the core dump filename pattern.
+///
+/// This is taken from `/proc/sys/kernel/core_pattern`.
+pub fn core_pattern() -> ProcResult<String> {
+    read_value("/proc/sys/kernel/core_pattern")
+}
+
+/// Sets the core dump filename pattern.
+///
+/// This writes to `/proc/sys/kernel/core_pattern`, and typically requires root.
+pub fn set_core_pattern(pattern: &str) -> ProcResult<()> {
+    write_value("/proc/sys/kernel/core_pattern", pattern)
+}
+
+/// Returns whether the `Ctrl-Alt-Del` keystroke triggers an immediate reboot
+/// (without shutting down services cleanly).
+///
+/// This is taken from `/proc/sys/kernel/ctrl-alt-del`.
+pub fn ctrl_alt_del() -> ProcResult<bool> {
+    let value: u8 = read_value("/proc/sys/kernel/ctrl-alt-del")?;
+    Ok(value != 0)
+}
+
+/// Sets whether the `Ctrl-Alt-Del` keystroke triggers an immediate reboot.
+///
+/// This writes to `/proc/sys/kernel/ctrl-alt-del`, and typically requires root.
+pub fn set_ctrl_alt_del(immediate_reboot: bool) -> ProcResult<()> {
+    write_value("/proc/sys/kernel/ctrl-alt-del", immediate_reboot as u8)
+}
+
+bitflags! {
+    /// Flags decoded from `/proc/sys/kernel/tainted`, describing why the
+    /// running kernel is tainted.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct TaintFlags: u64 {
+        /// A proprietary module was loaded.
+        const PROPRIETARY_MODULE = 1 << 0;
+        /// A module was force loaded.
+        const FORCED_MODULE = 1 << 1;
+        /// The kernel is running on an out-of-spec system.
+        const UNSAFE_SMP = 1 << 2;
+        /// A module was force unloaded.
+        const FORCED_RMMOD = 1 << 3;
+        /// A machine check exception occurred.
+        const MACHINE_CHECK = 1 << 4;
+        /// A page release function has found a bad page.
+        const BAD_PAGE = 1 << 5;
+        /// The user has asked that the kernel be marked tainted.
+        const USER = 1 << 6;
+        /// The kernel has oopsed before.
+        const DIE = 1 << 7;
+        /// An ACPI table was overridden by the user.
+        const OVERRIDDEN_ACPI_TABLE = 1 << 8;
+        /// A kernel warning has occurred.
+        const WARN = 1 << 9;
+        /// A module from `drivers/staging` was loaded.
+        const STAGING_DRIVER = 1 << 10;
+        /// The system is working around a severe firmware bug.
+        const FIRMWARE_WORKAROUND = 1 << 11;
+        /// An out-of-tree module was loaded.
+        const OUT_OF_TREE_MODULE = 1 << 12;
+        /// An unsigned module was loaded.
+        const UNSIGNED_MODULE = 1 << 13;
+        /// A soft lockup has previously occurred.
+        const SOFT_LOCKUP = 1 << 14;
+        /// The kernel has been live patched.
+        const LIVE_PATCHED = 1 << 15;
+        /// Auxiliary taint, defined and used by distros.
+        const AUX = 1 << 16;
+        /// The kernel was built with the struct randomization plugin.
+        const RANDSTRUCT = 1 << 17;
+    }
+}
+
+impl FromStr for TaintFlags {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits: u64 = s.trim().parse().map_err(|_| "Failed to parse tainted bitmask")?;
+        Ok(TaintFlags::from_bits_truncate(bits))
+    }
+}
+
+/// Returns the set of reasons the running kernel is tainted.
+///
+/// This is taken from `/proc/sys/kernel/tainted`.
+pub fn tainted() -> ProcResult<TaintFlags> {
+    read_value("/proc/sys/kernel/tainted")
+}
+
+/// Returns the maximum size, in bytes, of a single System V message queue message.
+///
+/// This is taken from `/proc/sys/kernel/msgmax`.
+pub fn msgmax() -> ProcResult<i64> {
+    read_value("/proc/sys/kernel/msgmax")
+}
+
+/// Sets the maximum size, in bytes, of a single System V message queue message.
+///
+/// This writes to `/proc/sys/kernel/msgmax`, and typically requires root.
+pub fn set_msgmax(max: i64) -> ProcResult<()> {
+    write_value("/proc/sys/kernel/msgmax", max)
+}
+
+/// Returns the maximum size, in bytes, of a single System V message queue.
+///
+/// This is taken from `/proc/sys/kernel/msgmnb`.
+pub fn msgmnb() -> ProcResult<i64> {
+    read_value("/proc/sys/kernel/msgmnb")
+}
+
+/// Sets the maximum size, in bytes, of a single System V message queue.
+///
+/// This writes to `/proc/sys/kernel/msgmnb`, and typically requires root.
+pub fn set_msgmnb(max: i64) -> ProcResult<()> {
+    write_value("/proc/sys/kernel/msgmnb", max)
+}
+
+/// Returns the system-wide limit on the number of System V message queues.
+///
+/// This is taken from `/proc/sys/kernel/msgmni`.
+pub fn msgmni() -> ProcResult<i64> {
+    read_value("/proc/sys/kernel/msgmni")
+}
+
+/// Sets the system-wide limit on the number of System V message queues.
+///
+/// This writes to `/proc/sys/kernel/msgmni`, and typically requires root.
+pub fn set_msgmni(max: i64) -> ProcResult<()> {
+    write_value("/proc/sys/kernel/msgmni", max)
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 /// Represents the data from `/proc/sys/kernel/sem`
 pub struct SemaphoreLimits {
@@ -175,6 +655,49 @@ impl FromStr for SemaphoreLimits {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// Represents the System V shared memory limits, gathered from
+/// `/proc/sys/kernel/shmmax`, `shmall`, and `shmmni`.
+pub struct SharedMemoryLimits {
+    /// The maximum size, in bytes, of a single shared memory segment
+    pub shmmax: u64,
+    /// The system-wide limit, in pages, on the total amount of shared memory
+    pub shmall: u64,
+    /// The system-wide limit on the number of shared memory segments
+    pub shmmni: u64,
+}
+
+impl SharedMemoryLimits {
+    pub fn new() -> ProcResult<Self> {
+        Ok(SharedMemoryLimits {
+            shmmax: read_value("/proc/sys/kernel/shmmax")?,
+            shmall: read_value("/proc/sys/kernel/shmall")?,
+            shmmni: read_value("/proc/sys/kernel/shmmni")?,
+        })
+    }
+
+    /// Sets the maximum size, in bytes, of a single shared memory segment.
+    ///
+    /// This writes to `/proc/sys/kernel/shmmax`, and typically requires root.
+    pub fn set_shmmax(shmmax: u64) -> ProcResult<()> {
+        write_value("/proc/sys/kernel/shmmax", shmmax)
+    }
+
+    /// Sets the system-wide limit, in pages, on the total amount of shared memory.
+    ///
+    /// This writes to `/proc/sys/kernel/shmall`, and typically requires root.
+    pub fn set_shmall(shmall: u64) -> ProcResult<()> {
+        write_value("/proc/sys/kernel/shmall", shmall)
+    }
+
+    /// Sets the system-wide limit on the number of shared memory segments.
+    ///
+    /// This writes to `/proc/sys/kernel/shmmni`, and typically requires root.
+    pub fn set_shmmni(shmmni: u64) -> ProcResult<()> {
+        write_value("/proc/sys/kernel/shmmni", shmmni)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +715,34 @@ mod tests {
         let a = Version::from_str("3.16.0_1").unwrap();
         let b = Version::new(3, 16, 0);
         assert_eq!(a, b);
+
+        let a = Version::from_str("6.1").unwrap();
+        let b = Version::new(6, 1, 0);
+        assert_eq!(a, b);
+
+        let a = Version::from_str("6").unwrap();
+        let b = Version::new(6, 0, 0);
+        assert_eq!(a, b);
+
+        let a = Version::from_str("5.10.102.1-microsoft-standard-WSL2").unwrap();
+        let b = Version::new(5, 10, 102);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_version_pre() {
+        let a = Version::from_str("6.1.0-rc3").unwrap();
+        let b = Version::new_rc(6, 1, 0, 3);
+        assert_eq!(a, b);
+
+        assert!(Version::new_rc(6, 1, 0, 2) < Version::new_rc(6, 1, 0, 3));
+        assert!(Version::new_rc(6, 1, 0, 3) < Version::new(6, 1, 0));
+        assert!(Version::new(6, 1, 0) > Version::new_rc(6, 1, 0, 3));
+        assert!(Version::new(6, 0, 9) < Version::new_rc(6, 1, 0, 1));
+
+        // Non-`-rcN` suffixes still parse, but carry no pre-release info.
+        let a = Version::from_str("3.16.0-6-amd64").unwrap();
+        assert_eq!(a.pre, None);
     }
 
     #[test]
@@ -204,6 +755,54 @@ mod tests {
         assert!(pid_max().is_ok());
     }
 
+    #[test]
+    fn test_threads_max() {
+        assert!(threads_max().is_ok());
+    }
+
+    #[test]
+    fn test_randomize_va_space() {
+        assert!(randomize_va_space().is_ok());
+
+        assert_eq!("0".parse(), Ok(RandomizeVaSpace::Disabled));
+        assert_eq!("1".parse(), Ok(RandomizeVaSpace::Conservative));
+        assert_eq!("2".parse(), Ok(RandomizeVaSpace::Full));
+        assert!("3".parse::<RandomizeVaSpace>().is_err());
+    }
+
+    #[test]
+    fn test_core_pattern() {
+        assert!(core_pattern().is_ok());
+    }
+
+    #[test]
+    fn test_ctrl_alt_del() {
+        assert!(ctrl_alt_del().is_ok());
+    }
+
+    #[test]
+    fn test_tainted() {
+        assert!(tainted().is_ok());
+
+        assert_eq!("0".parse(), Ok(TaintFlags::empty()));
+        assert_eq!(
+            "3".parse(),
+            Ok(TaintFlags::PROPRIETARY_MODULE | TaintFlags::FORCED_MODULE)
+        );
+    }
+
+    #[test]
+    fn test_message_queue_limits() {
+        assert!(msgmax().is_ok());
+        assert!(msgmnb().is_ok());
+        assert!(msgmni().is_ok());
+    }
+
+    #[test]
+    fn test_shared_memory_limits() {
+        let _ = SharedMemoryLimits::new().unwrap();
+    }
+
     #[test]
     fn test_semaphore_limits() {
         // Note that the below string has tab characters in it. Make sure to not remove them.
@@ -227,4 +826,60 @@ mod tests {
     fn test_sem() {
         let _ = SemaphoreLimits::new().unwrap();
     }
+
+    #[test]
+    fn test_version_req() {
+        let req: VersionReq = ">=4.9, <6.0".parse().unwrap();
+        assert!(req.matches(&Version::new(4, 9, 0)));
+        assert!(req.matches(&Version::new(5, 10, 1)));
+        assert!(!req.matches(&Version::new(4, 8, 9)));
+        assert!(!req.matches(&Version::new(6, 0, 0)));
+
+        let req: VersionReq = "~1.2.3".parse().unwrap();
+        assert!(req.matches(&Version::new(1, 2, 3)));
+        assert!(req.matches(&Version::new(1, 2, 9)));
+        assert!(!req.matches(&Version::new(1, 3, 0)));
+        assert!(!req.matches(&Version::new(1, 2, 2)));
+
+        let req: VersionReq = "~1.2".parse().unwrap();
+        assert!(req.matches(&Version::new(1, 2, 0)));
+        assert!(req.matches(&Version::new(1, 2, 99)));
+        assert!(!req.matches(&Version::new(1, 3, 0)));
+
+        let req: VersionReq = "^1.2.3".parse().unwrap();
+        assert!(req.matches(&Version::new(1, 2, 3)));
+        assert!(req.matches(&Version::new(1, 9, 9)));
+        assert!(!req.matches(&Version::new(2, 0, 0)));
+        assert!(!req.matches(&Version::new(1, 2, 2)));
+
+        let req: VersionReq = "^0.2.3".parse().unwrap();
+        assert!(req.matches(&Version::new(0, 2, 3)));
+        assert!(!req.matches(&Version::new(0, 3, 0)));
+
+        // Boundary values at the top of `u8` must not overflow/wrap when
+        // computing the exclusive upper bound.
+        let req: VersionReq = "~1.255".parse().unwrap();
+        assert!(req.matches(&Version::new(1, 255, 255)));
+        assert!(!req.matches(&Version::new(2, 0, 0)));
+
+        let req: VersionReq = "^255.0".parse().unwrap();
+        assert!(req.matches(&Version::new(255, 0, 0)));
+        assert!(req.matches(&Version::new(255, 255, 255)));
+
+        let req: VersionReq = "=1.2".parse().unwrap();
+        assert!(req.matches(&Version::new(1, 2, 0)));
+        assert!(req.matches(&Version::new(1, 2, 99)));
+        assert!(!req.matches(&Version::new(1, 3, 0)));
+
+        assert!("".parse::<VersionReq>().is_err());
+        assert_eq!(">=".parse::<VersionReq>(), Err(VersionReqError::MissingVersion));
+        assert_eq!(
+            "!1.2".parse::<VersionReq>(),
+            Err(VersionReqError::InvalidOperator("!1.2".to_string()))
+        );
+        assert_eq!(
+            ">=1.x".parse::<VersionReq>(),
+            Err(VersionReqError::InvalidVersion("1.x".to_string()))
+        );
+    }
 }